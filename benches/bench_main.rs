@@ -1,7 +1,8 @@
 #![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
 
 use criterion::{
-    black_box, criterion_group, criterion_main, Bencher, Benchmark, Criterion, Throughput,
+    black_box, criterion_group, criterion_main, Bencher, Benchmark, Criterion,
+    ParameterizedBenchmark, Throughput,
 };
 use num_traits::{One, Zero};
 use rand::{
@@ -10,10 +11,15 @@ use rand::{
 };
 use rand_xoshiro::{rand_core::SeedableRng, Xoshiro256Plus};
 use std::convert::TryInto;
-use substrate_fixed::{traits::Fixed, types::*};
+use substrate_fixed::{
+    traits::{Fixed, LossyInto},
+    transcendental::{cos, exp, exp_fast, ln, ln_fast, log2, sin, sin_iterations, tan},
+    types::*,
+};
 
 const SEED: u64 = 42_069;
 const DATASET_SIZE: usize = 10_000;
+const SIN_ITERATIONS: [u32; 5] = [8, 12, 16, 24, 32];
 
 fn gen_non_zero<T, R>(rng: &mut R) -> T
 where
@@ -40,6 +46,13 @@ where
         .collect()
 }
 
+fn gen_angle_dataset(cap: usize) -> Vec<f64> {
+    let mut rng: Xoshiro256Plus = Xoshiro256Plus::seed_from_u64(SEED);
+    (0..cap)
+        .map(|_| rng.gen_range(-std::f64::consts::PI, std::f64::consts::PI))
+        .collect()
+}
+
 fn fixed_point_op<F, O>(bencher: &mut Bencher, op: O)
 where
     F: Fixed,
@@ -169,5 +182,190 @@ create_bench!(bench_sub, "sub", |l, r| l - r);
 create_bench!(bench_mul, "mul", |l, r| l * r);
 create_bench!(bench_div, "div", |l, r| l / r);
 
-criterion_group!(benches, bench_add, bench_sub, bench_mul, bench_div);
+/// Prints the maximum absolute error of `sin_iterations` against `f64::sin`
+/// for every iteration count, to go with the timings of
+/// `bench_sin_iterations`.
+fn print_sin_accuracy(angles: &[f64]) {
+    println!("sin accuracy over {} angles in [-pi, pi]", angles.len());
+    println!("{:>10} {:>14} {:>14}", "iterations", "I32F32", "I64F64");
+    for &n in &SIN_ITERATIONS {
+        let mut max_error = (0f64, 0f64);
+        for &angle in angles {
+            let exact = angle.sin();
+            let r: f64 = sin_iterations(I32F32::from_num(angle), n)
+                .unwrap()
+                .lossy_into();
+            max_error.0 = max_error.0.max((r - exact).abs());
+            let r: f64 = sin_iterations(I64F64::from_num(angle), n)
+                .unwrap()
+                .lossy_into();
+            max_error.1 = max_error.1.max((r - exact).abs());
+        }
+        println!("{:>10} {:>14.3e} {:>14.3e}", n, max_error.0, max_error.1);
+    }
+}
+
+pub(crate) fn bench_sin_iterations(c: &mut Criterion) {
+    let angles = gen_angle_dataset(DATASET_SIZE);
+    print_sin_accuracy(&angles);
+    let narrow: Vec<I32F32> = angles.iter().map(|&a| I32F32::from_num(a)).collect();
+    let wide: Vec<I64F64> = angles.iter().map(|&a| I64F64::from_num(a)).collect();
+    c.bench(
+        "sin iterations",
+        ParameterizedBenchmark::new(
+            "FixedI64",
+            move |b, &n| {
+                b.iter(|| {
+                    for &angle in &narrow {
+                        black_box(sin_iterations(angle, n).unwrap());
+                    }
+                });
+            },
+            SIN_ITERATIONS.to_vec(),
+        )
+        .with_function("FixedI128", move |b, &n| {
+            b.iter(|| {
+                for &angle in &wide {
+                    black_box(sin_iterations(angle, n).unwrap());
+                }
+            });
+        })
+        .throughput(|_| Throughput::Elements(DATASET_SIZE.try_into().unwrap())),
+    );
+}
+
+pub(crate) fn bench_trigonometry(c: &mut Criterion) {
+    c.bench(
+        "trigonometry",
+        Benchmark::new("f64 sin", move |b| {
+            let angles = gen_angle_dataset(DATASET_SIZE);
+            b.iter(|| {
+                for &angle in &angles {
+                    black_box(angle.sin());
+                }
+            });
+        })
+        .with_function("FixedI64 sin", move |b| {
+            let angles: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(I32F32::from_num)
+                .collect();
+            b.iter(|| {
+                for &angle in &angles {
+                    black_box(sin(angle));
+                }
+            });
+        })
+        .with_function("FixedI64 cos", move |b| {
+            let angles: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(I32F32::from_num)
+                .collect();
+            b.iter(|| {
+                for &angle in &angles {
+                    black_box(cos(angle));
+                }
+            });
+        })
+        .with_function("FixedI64 tan", move |b| {
+            // stay clear of the poles at +-pi/2
+            let angles: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(|a| I32F32::from_num(a / 3.0))
+                .collect();
+            b.iter(|| {
+                for &angle in &angles {
+                    black_box(tan(angle));
+                }
+            });
+        })
+        .throughput(Throughput::Elements(DATASET_SIZE.try_into().unwrap())),
+    );
+}
+
+pub(crate) fn bench_exp_log(c: &mut Criterion) {
+    c.bench(
+        "exp and log",
+        Benchmark::new("FixedI64 exp", move |b| {
+            // e^x stays within I32F32 for x in [-8, 8]
+            let operands: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(|a| I32F32::from_num(a * 2.5))
+                .collect();
+            b.iter(|| {
+                for &x in &operands {
+                    black_box(exp::<I32F32, I32F32>(x).unwrap());
+                }
+            });
+        })
+        .with_function("FixedI64 exp_fast", move |b| {
+            let operands: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(|a| I32F32::from_num(a * 2.5))
+                .collect();
+            b.iter(|| {
+                for &x in &operands {
+                    black_box(exp_fast::<I32F32, I32F32>(x).unwrap());
+                }
+            });
+        })
+        .with_function("FixedI128 exp", move |b| {
+            let operands: Vec<I64F64> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(|a| I64F64::from_num(a * 2.5))
+                .collect();
+            b.iter(|| {
+                for &x in &operands {
+                    black_box(exp::<I64F64, I64F64>(x).unwrap());
+                }
+            });
+        })
+        .with_function("FixedI64 ln", move |b| {
+            // positive operands in (0, 2 pi]
+            let operands: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(|a| I32F32::from_num(a + std::f64::consts::PI) + I32F32::from_bits(1))
+                .collect();
+            b.iter(|| {
+                for &x in &operands {
+                    black_box(ln::<I32F32, I32F32>(x).unwrap());
+                }
+            });
+        })
+        .with_function("FixedI64 ln_fast", move |b| {
+            let operands: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(|a| I32F32::from_num(a + std::f64::consts::PI) + I32F32::from_bits(1))
+                .collect();
+            b.iter(|| {
+                for &x in &operands {
+                    black_box(ln_fast::<I32F32, I32F32>(x).unwrap());
+                }
+            });
+        })
+        .with_function("FixedI64 log2", move |b| {
+            let operands: Vec<I32F32> = gen_angle_dataset(DATASET_SIZE)
+                .into_iter()
+                .map(|a| I32F32::from_num(a + std::f64::consts::PI) + I32F32::from_bits(1))
+                .collect();
+            b.iter(|| {
+                for &x in &operands {
+                    black_box(log2::<I32F32, I32F32>(x).unwrap());
+                }
+            });
+        })
+        .throughput(Throughput::Elements(DATASET_SIZE.try_into().unwrap())),
+    );
+}
+
+criterion_group!(
+    benches,
+    bench_add,
+    bench_sub,
+    bench_mul,
+    bench_div,
+    bench_sin_iterations,
+    bench_trigonometry,
+    bench_exp_log
+);
 criterion_main!(benches);
@@ -0,0 +1,177 @@
+//! Feeds raw bit patterns into every public function of
+//! `substrate_fixed::transcendental`. Every call has to return, either a
+//! value or an error; any panic is a bug.
+//!
+//! Run with `cargo fuzz run transcendental` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use substrate_fixed::transcendental::*;
+use substrate_fixed::types::{I0F32, I16F16, I1F31, I32F32, I64F64, I9F23, U0F32, U64F64};
+
+/// reads raw bit patterns from the fuzzer input, padding with zeros
+struct Bits<'a>(&'a [u8]);
+
+impl Bits<'_> {
+    fn u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        let n = self.0.len().min(8);
+        bytes[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        u64::from_le_bytes(bytes)
+    }
+
+    fn i32(&mut self) -> i32 {
+        self.u64() as i32
+    }
+
+    fn i64(&mut self) -> i64 {
+        self.u64() as i64
+    }
+
+    fn i128(&mut self) -> i128 {
+        i128::from(self.u64()) << 64 | i128::from(self.u64())
+    }
+}
+
+/// calls every generic function with signed operands of type `$T`
+macro_rules! signed {
+    ($T:ty, $a:expr, $b:expr, $c:expr, $i:expr) => {{
+        let (a, b, c, i) = (
+            <$T>::from_bits($a),
+            <$T>::from_bits($b),
+            <$T>::from_bits($c),
+            $i,
+        );
+        let _ = clamp01(a);
+        let _ = clamp_unit(a);
+        let _ = sqrt::<$T, $T>(a);
+        let _ = cbrt::<$T, $T>(a);
+        let _ = nth_root::<$T, $T>(a, i as u32 % 16);
+        let _ = sqrt_rem(a.int());
+        let _ = sqrt_diff::<$T, $T>(a, b);
+        let _ = hypot::<$T, $T>(a, b);
+        let _ = log2::<$T, $T>(a);
+        let _ = log2_arith::<$T, $T>(a);
+        let _ = log2_fast::<$T, $T>(a);
+        let _ = log2_unsigned::<$T, $T>(a);
+        let _ = log2_from_parts(a, i);
+        let _ = ln::<$T, $T>(a);
+        let _ = ln_arith::<$T, $T>(a);
+        let _ = ln_fast::<$T, $T>(a);
+        let _ = log2_refined::<$T, $T>(a);
+        let _ = ln_refined::<$T, $T>(a);
+        let _ = ln_unsigned::<$T, $T>(a);
+        let _ = ln_product::<$T, $T>(&[a, b, c]);
+        let _ = exp::<$T, $T>(a);
+        let _ = exp_compensated::<$T, $T>(a);
+        let _ = exp_fast::<$T, $T>(a);
+        let _ = sigmoid(a);
+        let _ = catenary(a, b);
+        let _ = exp_with_bound::<$T, $T>(a);
+        let _ = exp_with_underflow::<$T, $T>(a);
+        let _ = clamp_to_exp_range::<$T, $T>(a);
+        let _ = clamp_to_ln_domain::<$T, $T>(a);
+        let _ = pow::<$T, $T>(a, b);
+        let _ = pow_compensated::<$T, $T>(a, b);
+        let _ = pow_capped::<$T, $T>(a, b, c);
+        let _ = PowContext::<$T, $T>::new(a).pow(b);
+        let _ = digamma::<$T, $T>(a);
+        let _ = sinh(a);
+        let _ = cosh(a);
+        let _ = tanh(a);
+        let _ = asinh(a);
+        let _ = acosh(a);
+        let _ = atanh(a);
+        let _ = exp_parts::<$T, $T>(a);
+        let _ = pow_parts::<$T, $T>(a, b);
+        let _ = powi::<$T, $T>(a, i);
+        let _ = powi_wide::<$T, $T, $T>(a, i);
+        let _ = powi_wide::<$T, I64F64, $T>(a, i);
+        let _ = composite_key(&[a, b, c]);
+        let _ = cheb_eval(a, &[b, c]);
+        let _ = reduce_angle(a);
+        let _ = sin(a);
+        let _ = sin_iterations(a, i as u32 % 160);
+        let _ = sin_with_bound(a);
+        let _ = cos(a);
+        let _ = sin_deg_table::<$T>(i);
+        let _ = cos_deg_table::<$T>(i);
+        let _ = tan(a);
+        let _ = wave(a, b, c);
+        let _ = asin_bisection(a);
+        let _ = acos_bisection(a);
+        let _ = atan_bisection(a);
+        let _ = atan(a);
+        let _ = atan2(a, b);
+        let _ = log2_ratio(a, b);
+        let _ = ln_ratio(a, b);
+        let _ = log_base(a, b);
+        let _ = weighted_geomean(&[a, b], &[c, a]);
+        let _ = exp_or_else::<$T, $T, _>(a, |_| b);
+        let _ = try_into_narrower::<$T, I9F23>(exp::<$T, $T>(a));
+        let _ = pow_or_else::<$T, $T, _>(a, b, |_, _| c);
+        let _ = bearing(a, b);
+        for config in &[TranscendentalConfig::fast(), TranscendentalConfig::precise()] {
+            let _ = config.sin(a);
+            let _ = config.cos(a);
+            let _ = config.exp::<$T, $T>(a);
+            let _ = config.pow::<$T, $T>(a, b);
+            let _ = config.log2::<$T, $T>(a);
+            let _ = config.ln::<$T, $T>(a);
+        }
+        let mut rotator = Rotator::new(a, b);
+        for _ in 0..20 {
+            let _ = rotator.step();
+        }
+        let mut phasor = Phasor::new(a);
+        let _ = phasor.advance(b);
+        let _ = phasor.advance(c);
+        let _ = rotate_point(a, b, c);
+        let _ = asin(a);
+        let _ = acos(a);
+    }};
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut bits = Bits(data);
+    let (x, y, z, i) = (bits.i128(), bits.i128(), bits.i128(), bits.i32());
+
+    signed!(I9F23, x as i32, y as i32, z as i32, i);
+    signed!(I16F16, x as i32, y as i32, z as i32, i);
+    signed!(I32F32, x as i64, y as i64, z as i64, i);
+    signed!(I64F64, x, y, z, i);
+
+    let _ = sqrt::<U64F64, U64F64>(U64F64::from_bits(x as u128));
+    let _ = sqrt_rem(U64F64::from_bits(x as u128).int());
+    let _ = sqrt::<I0F32, I0F32>(I0F32::from_bits(x as i32));
+    let _ = sqrt::<U0F32, U0F32>(U0F32::from_bits(x as u32));
+    let _ = log2::<I0F32, I32F32>(I0F32::from_bits(x as i32));
+    let _ = ln::<I0F32, I32F32>(I0F32::from_bits(x as i32));
+    let _ = exp::<I0F32, I32F32>(I0F32::from_bits(x as i32));
+    let _ = clamp01(I0F32::from_bits(x as i32));
+    let _ = clamp_unit(I0F32::from_bits(x as i32));
+    let _ = log2_unsigned::<U64F64, I64F64>(U64F64::from_bits(x as u128));
+    let _ = ln_unsigned::<U64F64, I64F64>(U64F64::from_bits(x as u128));
+
+    let _ = sin_into::<I1F31>(I9F23::from_bits(x as i32));
+    let _ = cos_into::<I1F31>(I9F23::from_bits(x as i32));
+
+    let _ = sqrt_i32f32(I32F32::from_bits(x as i64));
+    let _ = log2_i32f32(I32F32::from_bits(x as i64));
+    let _ = ln_i32f32(I32F32::from_bits(x as i64));
+    let _ = exp_i32f32(I32F32::from_bits(x as i64));
+
+    let (p, q) = (bits.i64(), bits.i64());
+    let frac_bits = i as u32 % 80;
+    let _ = log2_floor(p as u64);
+    let _ = log2_ceil(p as u64);
+    let _ = ln_scaled(p, frac_bits);
+    let _ = exp_scaled(p, frac_bits);
+    let _ = ln_rational::<I32F32>(p, q);
+    let _ = sqrt_rational::<I32F32>(p, q);
+    let _ = from_per_unit::<I32F32>(p as u64, q as u64);
+    let _ = to_per_unit(I32F32::from_bits(x as i64), q as u64);
+    let _ = pow_per_unit(p as u64, q as u64, I32F32::from_bits(y as i64));
+});
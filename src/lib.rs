@@ -146,22 +146,30 @@ it in your crate, add it as a dependency inside [*Cargo.toml*]:
 fixed = "0.5.4"
 ```
 
-The *fixed* crate requires rustc version 1.39.0 or later.
+The *fixed* crate requires rustc version 1.46.0 or later.
 
 ## Optional features
 
-The *fixed* crate has four optional feature:
+The *fixed* crate has six optional feature:
 
  1. `az`, disabled by default. This implements the cast traits
     provided by the [*az* crate].
- 2. `f16`, disabled by default. This provides conversion to/from
+ 2. `deg-table`, disabled by default. This provides sine and cosine
+    of whole degrees from a 91-entry lookup table in the
+    `transcendental` module.
+ 3. `f16`, disabled by default. This provides conversion to/from
     [`f16`] and [`bf16`]. This features requires the [*half* crate].
- 3. `serde`, disabled by default. This provides serialization support
+ 4. `serde`, disabled by default. This provides serialization support
     for the fixed-point types. This feature requires the
     [*serde* crate].
- 4. `std`, disabled by default. This is for features that are not
+ 5. `std`, disabled by default. This is for features that are not
     possible under `no_std`: currently the implementation of the
-    [`Error`] trait for [`ParseFixedError`].
+    [`Error`] trait for [`ParseFixedError`], and of the
+    `transcendental::Transcendental` trait for `f32` and `f64`.
+ 6. `telemetry`, disabled by default. This counts the calls, series
+    terms and errors of the exponential and power functions, and the
+    roundings in the bit loop of the logarithms, in the `transcendental`
+    module, for profiling off-chain code.
 
 To enable features, you can add the dependency like this to
 [*Cargo.toml*]:
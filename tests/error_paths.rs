@@ -0,0 +1,118 @@
+#![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
+
+// The counting allocator is global, so it lives in its own test binary
+// instead of replacing the allocator of every unit test. This file holds
+// a single test, so no other test allocates while it counts.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ops::{AddAssign, BitOrAssign, ShlAssign};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use substrate_fixed::{
+    traits::{FixedSigned, ToFixed},
+    transcendental::{
+        ln, ln_arith, ln_fast, ln_i32f32, ln_refined, log2, log2_arith, log2_fast, log2_i32f32,
+        log2_refined, log2_unsigned, sqrt, sqrt_i32f32,
+    },
+    types::{I0F32, I2F30, I32F32, I4F28, I9F23, U64F64},
+};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// the system allocator, counting every allocation
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// result of `f` and the number of allocations it made
+fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let result = f();
+    (result, ALLOCATIONS.load(Ordering::SeqCst) - before)
+}
+
+/// every variant of the logarithms
+fn logs<T>(operand: T) -> [Result<T, ()>; 8]
+where
+    T: FixedSigned + PartialOrd<I9F23>,
+    T::Bits: Copy + ToFixed + AddAssign + BitOrAssign + ShlAssign,
+{
+    [
+        log2::<T, T>(operand),
+        log2_arith::<T, T>(operand),
+        log2_fast::<T, T>(operand),
+        log2_refined::<T, T>(operand),
+        ln::<T, T>(operand),
+        ln_arith::<T, T>(operand),
+        ln_fast::<T, T>(operand),
+        ln_refined::<T, T>(operand),
+    ]
+}
+
+#[test]
+fn error_paths_are_clean() {
+    // the counting works
+    assert_eq!(count_allocations(|| vec![0u8]).1, 1);
+
+    // every error branch of the logarithms returns early
+    type D = I32F32;
+    for &x in &[
+        D::from_num(0),
+        D::from_num(-1),
+        D::from_bits(-1),
+        D::min_value(),
+    ] {
+        let (results, allocations) = count_allocations(|| logs(x));
+        assert_eq!(results, [Err(()); 8]);
+        assert_eq!(allocations, 0);
+        let (results, allocations) = count_allocations(|| {
+            [
+                log2_i32f32(x),
+                ln_i32f32(x),
+                log2_unsigned::<U64F64, D>(U64F64::from_num(0)),
+            ]
+        });
+        assert_eq!(results, [Err(()); 3]);
+        assert_eq!(allocations, 0);
+    }
+    // too few integer bits, and an integer part below the minimum of -8
+    let (results, allocations) = count_allocations(|| logs(I2F30::from_num(1.5)));
+    assert_eq!(results, [Err(()); 8]);
+    assert_eq!(allocations, 0);
+    let (results, allocations) = count_allocations(|| logs(I4F28::from_bits(1)));
+    assert_eq!(results, [Err(()); 8]);
+    assert_eq!(allocations, 0);
+
+    // the error branches of sqrt: a negative operand and a root beyond the
+    // range of I0F32
+    let (results, allocations) = count_allocations(|| {
+        [
+            sqrt::<D, D>(D::from_num(-1)),
+            sqrt::<D, D>(D::min_value()),
+            sqrt::<I0F32, I0F32>(I0F32::from_num(0.3)).map(D::from_num),
+        ]
+    });
+    assert_eq!(
+        results,
+        [
+            Err("Can't calculate sqrt from negative numbers."),
+            Err("Can't calculate sqrt from negative numbers."),
+            Err("Overflow calculating sqrt."),
+        ]
+    );
+    assert_eq!(allocations, 0);
+    let (result, allocations) = count_allocations(|| sqrt_i32f32(D::from_num(-1)));
+    assert_eq!(result, Err(()));
+    assert_eq!(allocations, 0);
+}